@@ -1,12 +1,92 @@
+use std::borrow::Cow;
 use std::fmt::Display;
 
+/// Location of a token in the source, as a half-open byte range plus the
+/// line/column of its first character (both 1-based).
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// A lexing failure, carrying the [`Span`] of the offending input so it can be
+/// rendered as a snippet-with-caret diagnostic via [`LexError::render`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum LexError {
+    UnterminatedString(Span),
+    IntegerOverflow(Span),
+    UnexpectedChar(char, Span),
+    MalformedNumber(Span),
+    EmptyChar(Span),
+    UnterminatedChar(Span),
+    InvalidEscape(char, Span),
+    UnterminatedComment(Span),
+}
+
+impl LexError {
+    pub fn span(&self) -> Span {
+        match self {
+            LexError::UnterminatedString(span)
+            | LexError::IntegerOverflow(span)
+            | LexError::UnexpectedChar(_, span)
+            | LexError::MalformedNumber(span)
+            | LexError::EmptyChar(span)
+            | LexError::UnterminatedChar(span)
+            | LexError::InvalidEscape(_, span)
+            | LexError::UnterminatedComment(span) => *span,
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            LexError::UnterminatedString(_) => "unterminated string literal".to_string(),
+            LexError::IntegerOverflow(_) => "integer literal is too large".to_string(),
+            LexError::UnexpectedChar(ch, _) => format!("unexpected character {ch:?}"),
+            LexError::MalformedNumber(_) => "malformed number literal".to_string(),
+            LexError::EmptyChar(_) => "empty character literal".to_string(),
+            LexError::UnterminatedChar(_) => "unterminated character literal".to_string(),
+            LexError::InvalidEscape(ch, _) => format!("invalid escape sequence {:?}", format!("\\{ch}")),
+            LexError::UnterminatedComment(_) => "unterminated block comment".to_string(),
+        }
+    }
+
+    /// Render the error against its source as a line of context with a caret
+    /// underlining the offending span, in the style of diagnostics-oriented
+    /// interpreters.
+    pub fn render(&self, source: &str) -> String {
+        let span = self.span();
+        let line_text = source.lines().nth(span.line - 1).unwrap_or("");
+        let width = span.end.saturating_sub(span.start).max(1);
+        let gutter = span.line.to_string();
+        let pad = " ".repeat(gutter.len());
+        format!(
+            "error: {message}\n{pad} --> line {line}, column {column}\n{pad} |\n{gutter} | {line_text}\n{pad} | {caret_pad}{carets}",
+            message = self.message(),
+            line = span.line,
+            column = span.column,
+            caret_pad = " ".repeat(span.column - 1),
+            carets = "^".repeat(width),
+        )
+    }
+}
+
+impl Display for LexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
-pub enum Token {
+pub enum Token<'src> {
     Illegal,
     Eof,
-    Ident(String),
+    Ident(&'src str),
     Int(isize),
-    String(String),
+    Float(f64),
+    Char(char),
+    String(Cow<'src, str>),
     Assign,
 
     Plus,
@@ -38,7 +118,7 @@ pub enum Token {
     Produce,
 }
 
-impl Display for Token {
+impl Display for Token<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Token::Ident(s) => write!(f, "Int({})", s),
@@ -50,7 +130,7 @@ impl Display for Token {
     }
 }
 
-impl From<char> for Token {
+impl From<char> for Token<'_> {
     fn from(c: char) -> Self {
         match c {
             '\0' => Self::Eof,
@@ -76,9 +156,9 @@ impl From<char> for Token {
     }
 }
 
-impl From<String> for Token {
-    fn from(s: String) -> Self {
-        match s.as_str() {
+impl<'src> From<&'src str> for Token<'src> {
+    fn from(s: &'src str) -> Self {
+        match s {
             "!=" => Self::NotEq,
             "==" => Self::Eq,
             "atom" => Self::Atom,
@@ -100,40 +180,56 @@ impl From<String> for Token {
     }
 }
 
-pub struct Lexer {
-    pub input: String,
+pub struct Lexer<'src> {
+    pub input: &'src str,
     pub position: usize,
     pub read_position: usize,
     pub ch: char,
+    pub line: usize,
+    pub column: usize,
+    pub errors: Vec<LexError>,
+    finished: bool,
 }
 
-impl Lexer {
-    pub fn new(input: String) -> Self {
+impl<'src> Lexer<'src> {
+    pub fn new(input: &'src str) -> Self {
         let mut l = Self {
             input,
             position: 0,
             read_position: 0,
             ch: '\0',
+            line: 1,
+            column: 0,
+            errors: Vec::new(),
+            finished: false,
         };
         l.read_char();
         l
     }
 
     pub fn read_char(&mut self) {
+        if self.ch == '\n' {
+            self.line += 1;
+            self.column = 0;
+        }
         if self.read_position >= self.input.len() {
             self.ch = '\0';
+            self.position = self.read_position;
+            self.read_position += 1;
         } else {
-            self.ch = self.input.chars().nth(self.read_position).unwrap();
+            let c = self.input[self.read_position..].chars().next().unwrap();
+            self.ch = c;
+            self.position = self.read_position;
+            self.read_position += c.len_utf8();
         }
-        self.position = self.read_position;
-        self.read_position += 1;
+        self.column += 1;
     }
 
     pub fn peek_char(&self) -> char {
         if self.read_position >= self.input.len() {
             '\0'
         } else {
-            self.input.chars().nth(self.read_position).unwrap()
+            self.input[self.read_position..].chars().next().unwrap()
         }
     }
 
@@ -143,68 +239,362 @@ impl Lexer {
         }
     }
 
-    pub fn read_identifer(&mut self) -> String {
-        let mut ident = String::new();
+    /// Skip over whitespace and comments until the next significant
+    /// character. `//` runs to end of line; `/* ... */` blocks nest via a depth
+    /// counter and report [`LexError::UnterminatedComment`] if EOF is hit
+    /// first. Comments are transparent to the token stream, just like
+    /// whitespace.
+    pub fn skip_trivia(&mut self) -> Result<(), LexError> {
+        loop {
+            self.skip_whitespace();
+            if self.ch == '/' && self.peek_char() == '/' {
+                while self.ch != '\n' && self.ch != '\0' {
+                    self.read_char();
+                }
+            } else if self.ch == '/' && self.peek_char() == '*' {
+                self.skip_block_comment()?;
+            } else {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn skip_block_comment(&mut self) -> Result<(), LexError> {
+        let start = self.position;
+        let line = self.line;
+        let column = self.column;
+        self.read_char(); // '/'
+        self.read_char(); // '*'
+        let mut depth = 1;
+        while depth > 0 {
+            match self.ch {
+                '\0' => {
+                    return Err(LexError::UnterminatedComment(Span {
+                        start,
+                        end: self.position,
+                        line,
+                        column,
+                    }))
+                }
+                '/' if self.peek_char() == '*' => {
+                    self.read_char();
+                    self.read_char();
+                    depth += 1;
+                }
+                '*' if self.peek_char() == '/' => {
+                    self.read_char();
+                    self.read_char();
+                    depth -= 1;
+                }
+                _ => self.read_char(),
+            }
+        }
+        Ok(())
+    }
+
+    pub fn read_identifer(&mut self) -> &'src str {
+        let start = self.position;
         while self.ch.is_ascii_alphanumeric() || self.ch == '_' {
-            ident.push(self.ch);
             self.read_char();
         }
-        ident
+        &self.input[start..self.position]
     }
 
-    pub fn read_number(&mut self) -> String {
-        let mut number = String::new();
+    /// Scan an integer run, or a floating-point literal when a `.` is followed
+    /// by a digit. Any other `.` after the digits, a second one (e.g. `1.2.3`)
+    /// or one not followed by a digit (e.g. `1.`), is rejected as a
+    /// [`LexError::MalformedNumber`].
+    pub fn read_number(&mut self) -> Result<Token<'src>, LexError> {
+        let start = self.position;
+        let line = self.line;
+        let column = self.column;
         while self.ch.is_ascii_digit() {
-            number.push(self.ch);
             self.read_char();
         }
-        number
-    }
 
-    pub fn read_string(&mut self) -> String {
-        let mut string = String::new();
-        self.read_char();
-        while self.ch != '"' {
-            string.push(self.ch);
+        let mut is_float = false;
+        if self.ch == '.' && self.peek_char().is_ascii_digit() {
+            is_float = true;
             self.read_char();
+            while self.ch.is_ascii_digit() {
+                self.read_char();
+            }
+        }
+
+        if self.ch == '.' {
+            // A point that does not start a fraction (a second one, or one
+            // with no digit after it): consume the rest of the run so the
+            // error span covers the whole malformed literal.
+            self.read_char();
+            while self.ch.is_ascii_digit() || self.ch == '.' {
+                self.read_char();
+            }
+            return Err(LexError::MalformedNumber(Span {
+                start,
+                end: self.position,
+                line,
+                column,
+            }));
         }
 
+        let slice = &self.input[start..self.position];
+        if is_float {
+            slice.parse::<f64>().map(Token::Float).map_err(|_| {
+                LexError::MalformedNumber(Span {
+                    start,
+                    end: self.position,
+                    line,
+                    column,
+                })
+            })
+        } else {
+            slice.parse::<isize>().map(Token::Int).map_err(|_| {
+                LexError::IntegerOverflow(Span {
+                    start,
+                    end: self.position,
+                    line,
+                    column,
+                })
+            })
+        }
+    }
+
+    pub fn read_string(&mut self) -> Result<Cow<'src, str>, LexError> {
+        let quote_start = self.position;
+        let line = self.line;
+        let column = self.column;
         self.read_char();
-        string
+        let start = self.position;
+        // Stay borrowed until the first escape forces an owned copy.
+        let mut decoded: Option<String> = None;
+        loop {
+            match self.ch {
+                '"' => break,
+                '\0' => {
+                    return Err(LexError::UnterminatedString(Span {
+                        start: quote_start,
+                        end: self.position,
+                        line,
+                        column,
+                    }))
+                }
+                '\\' => {
+                    let buf =
+                        decoded.get_or_insert_with(|| self.input[start..self.position].to_string());
+                    let escape_start = self.position;
+                    let escape_line = self.line;
+                    let escape_column = self.column;
+                    self.read_char();
+                    buf.push(self.decode_escape(escape_start, escape_line, escape_column)?);
+                    self.read_char();
+                }
+                ch => {
+                    if let Some(buf) = decoded.as_mut() {
+                        buf.push(ch);
+                    }
+                    self.read_char();
+                }
+            }
+        }
+        let string = match decoded {
+            Some(owned) => Cow::Owned(owned),
+            None => Cow::Borrowed(&self.input[start..self.position]),
+        };
+        self.read_char();
+        Ok(string)
     }
 
-    pub fn next_token(&mut self) -> Token {
-        self.skip_whitespace();
+    /// Read a single-quoted character literal, decoding the escape sequences
+    /// `\n`, `\t`, `\\` and `\'`. Errors on an empty `''` or a missing closing
+    /// quote.
+    pub fn read_char_literal(&mut self) -> Result<char, LexError> {
+        let quote_start = self.position;
+        let line = self.line;
+        let column = self.column;
+        self.read_char();
 
-        let token = match self.ch {
-            'a'..='z' | 'A'..='Z' | '_' => {
-                return Token::from(self.read_identifer());
+        let value = match self.ch {
+            '\'' => {
+                self.read_char();
+                return Err(LexError::EmptyChar(Span {
+                    start: quote_start,
+                    end: self.position,
+                    line,
+                    column,
+                }));
             }
-            '0'..='9' => {
-                return Token::Int(self.read_number().parse::<isize>().unwrap());
+            '\0' => {
+                return Err(LexError::UnterminatedChar(Span {
+                    start: quote_start,
+                    end: self.position,
+                    line,
+                    column,
+                }))
             }
-            '"' => {
-                return Token::String(self.read_string());
+            '\\' => {
+                let escape_start = self.position;
+                let escape_line = self.line;
+                let escape_column = self.column;
+                self.read_char();
+                self.decode_escape(escape_start, escape_line, escape_column)?
             }
+            ch => ch,
+        };
+        self.read_char();
+
+        if self.ch != '\'' {
+            return Err(LexError::UnterminatedChar(Span {
+                start: quote_start,
+                end: self.position,
+                line,
+                column,
+            }));
+        }
+        self.read_char();
+        Ok(value)
+    }
+
+    /// Decode the escape sequence whose backslash has already been consumed,
+    /// with `self.ch` positioned on the escape character. Shared by string and
+    /// character literals.
+    fn decode_escape(
+        &self,
+        start: usize,
+        line: usize,
+        column: usize,
+    ) -> Result<char, LexError> {
+        match self.ch {
+            'n' => Ok('\n'),
+            't' => Ok('\t'),
+            '\\' => Ok('\\'),
+            '\'' => Ok('\''),
+            other => Err(LexError::InvalidEscape(
+                other,
+                Span {
+                    start,
+                    end: self.position,
+                    line,
+                    column,
+                },
+            )),
+        }
+    }
+
+    /// Produce the next token along with the [`Span`] it occupies in the
+    /// source. Location bookkeeping is maintained in [`read_char`], so this is
+    /// the primitive the parser builds diagnostics on; [`next_token`] is a thin
+    /// wrapper that throws the span away.
+    ///
+    /// [`read_char`]: Self::read_char
+    /// [`next_token`]: Self::next_token
+    pub fn next_spanned(&mut self) -> (Token<'src>, Span) {
+        if let Err(error) = self.skip_trivia() {
+            let span = error.span();
+            self.errors.push(error);
+            return (Token::Illegal, span);
+        }
+
+        let start = self.position;
+        let line = self.line;
+        let column = self.column;
+
+        let result: Result<Token<'src>, LexError> = match self.ch {
+            'a'..='z' | 'A'..='Z' | '_' => Ok(Token::from(self.read_identifer())),
+            '0'..='9' => self.read_number(),
+            '"' => self.read_string().map(Token::String),
+            '\'' => self.read_char_literal().map(Token::Char),
             '=' | '!' => {
                 if self.peek_char() == '=' {
-                    let mut s = String::new();
-                    s.push(self.ch);
+                    let op_start = self.position;
+                    self.read_char();
                     self.read_char();
-                    s.push(self.ch);
-                    Token::from(s)
+                    Ok(Token::from(&self.input[op_start..self.position]))
                 } else {
-                    Token::from(self.ch)
+                    let t = Token::from(self.ch);
+                    self.read_char();
+                    Ok(t)
+                }
+            }
+            _ => {
+                let ch = self.ch;
+                let t = Token::from(self.ch);
+                self.read_char();
+                if t == Token::Illegal {
+                    Err(LexError::UnexpectedChar(
+                        ch,
+                        Span {
+                            start,
+                            end: self.position,
+                            line,
+                            column,
+                        },
+                    ))
+                } else {
+                    Ok(t)
                 }
             }
-            _ => Token::from(self.ch),
         };
 
-        self.read_char();
-        token
+        let span = Span {
+            start,
+            end: self.position,
+            line,
+            column,
+        };
+
+        let token = match result {
+            Ok(token) => token,
+            Err(error) => {
+                self.errors.push(error);
+                Token::Illegal
+            }
+        };
+
+        (token, span)
+    }
+
+    pub fn next_token(&mut self) -> Token<'src> {
+        self.next_spanned().0
     }
 }
 
+/// Streaming view of the token stream: yields each [`Token`] in turn and
+/// returns `None` once the first [`Token::Eof`] has been produced, so a `for`
+/// loop terminates naturally. Any [`LexError`]s are still collected in
+/// [`Lexer::errors`]. Use [`lex`] when the whole stream is wanted at once.
+impl<'src> Iterator for Lexer<'src> {
+    type Item = Token<'src>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let token = self.next_token();
+        if token == Token::Eof {
+            self.finished = true;
+        }
+        Some(token)
+    }
+}
+
+/// Drive a fresh lexer over `input` to completion, returning every token with
+/// its [`Span`] (including the terminating [`Token::Eof`]). The batch companion
+/// to the streaming [`Iterator`] impl.
+pub fn lex(input: &str) -> Vec<(Token<'_>, Span)> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+    loop {
+        let spanned = lexer.next_spanned();
+        let is_eof = spanned.0 == Token::Eof;
+        tokens.push(spanned);
+        if is_eof {
+            break;
+        }
+    }
+    tokens
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -221,12 +611,12 @@ mod tests {
         "#,
         );
 
-        let mut lexer = Lexer::new(input);
+        let mut lexer = Lexer::new(&input);
 
-        let tests = vec![
-            Token::String(String::from("foobar")),
-            Token::String(String::from("foo bar")),
-            Token::String(String::from("1")),
+        let tests = [
+            Token::String("foobar".into()),
+            Token::String("foo bar".into()),
+            Token::String("1".into()),
         ];
 
         for (i, t) in tests.iter().enumerate() {
@@ -235,6 +625,120 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_float_and_char() {
+        let input = String::from(r#"2.5 'a' '\n' "a\tb""#);
+        let mut lexer = Lexer::new(&input);
+
+        let tests = [
+            Token::Float(2.5),
+            Token::Char('a'),
+            Token::Char('\n'),
+            Token::String("a\tb".into()),
+            Token::Eof,
+        ];
+
+        for (i, t) in tests.iter().enumerate() {
+            let tok = lexer.next_token();
+            assert_eq!(tok, *t, "Test index: {i} | Expected: {t:?} | Got: {tok:?}");
+        }
+        assert!(lexer.errors.is_empty(), "errors: {:?}", lexer.errors);
+    }
+
+    #[test]
+    fn test_iterator_and_lex() {
+        let collected: Vec<Token> = Lexer::new("atom x = 5;").collect();
+        assert_eq!(
+            collected,
+            vec![
+                Token::Atom,
+                Token::Ident("x"),
+                Token::Assign,
+                Token::Int(5),
+                Token::Semicolon,
+                Token::Eof,
+            ]
+        );
+
+        let tokens = lex("atom x = 5;");
+        assert_eq!(tokens.len(), 6);
+        assert_eq!(tokens.first().unwrap().0, Token::Atom);
+        assert_eq!(tokens.last().unwrap().0, Token::Eof);
+    }
+
+    #[test]
+    fn test_comments() {
+        let input = String::from(
+            r#"
+        // a line comment
+        atom x = 5; // trailing
+        /* a block /* nested */ comment */
+        atom y = 10;
+        "#,
+        );
+        let mut lexer = Lexer::new(&input);
+
+        let tests = [
+            Token::Atom,
+            Token::Ident("x"),
+            Token::Assign,
+            Token::Int(5),
+            Token::Semicolon,
+            Token::Atom,
+            Token::Ident("y"),
+            Token::Assign,
+            Token::Int(10),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        for (i, t) in tests.iter().enumerate() {
+            let tok = lexer.next_token();
+            assert_eq!(tok, *t, "Test index: {i} | Expected: {t:?} | Got: {tok:?}");
+        }
+        assert!(lexer.errors.is_empty(), "errors: {:?}", lexer.errors);
+
+        let mut lexer = Lexer::new("/* never closed");
+        while lexer.next_token() != Token::Eof {}
+        assert!(matches!(
+            lexer.errors.as_slice(),
+            [LexError::UnterminatedComment(_)]
+        ));
+    }
+
+    #[test]
+    fn test_char_and_number_errors() {
+        let mut lexer = Lexer::new("''");
+        while lexer.next_token() != Token::Eof {}
+        assert!(matches!(lexer.errors.as_slice(), [LexError::EmptyChar(_)]));
+
+        let mut lexer = Lexer::new("1.2.3");
+        while lexer.next_token() != Token::Eof {}
+        assert!(matches!(
+            lexer.errors.as_slice(),
+            [LexError::MalformedNumber(_)]
+        ));
+    }
+
+    #[test]
+    fn test_lex_errors() {
+        let input = String::from("\"oops");
+        let mut lexer = Lexer::new(&input);
+        while lexer.next_token() != Token::Eof {}
+        assert!(matches!(
+            lexer.errors.as_slice(),
+            [LexError::UnterminatedString(_)]
+        ));
+
+        let overflow = format!("{}0", isize::MAX);
+        let mut lexer = Lexer::new(&overflow);
+        while lexer.next_token() != Token::Eof {}
+        assert!(matches!(
+            lexer.errors.as_slice(),
+            [LexError::IntegerOverflow(_)]
+        ));
+    }
+
     #[test]
     fn test_next_token() {
         let input = String::from(
@@ -248,7 +752,7 @@ mod tests {
 
         molecule result = add(five, ten);
 
-        !-/*5;
+        !-/ *5;
         5 < 10 > 5;
 
         if (5 < 10) {
@@ -267,41 +771,41 @@ mod tests {
         "#,
         );
 
-        let mut lexer = Lexer::new(input);
+        let mut lexer = Lexer::new(&input);
 
         let tests = vec![
             Token::Atom,
-            Token::Ident(String::from("five")),
+            Token::Ident("five"),
             Token::Assign,
             Token::Int(5),
             Token::Semicolon,
             Token::Atom,
-            Token::Ident(String::from("ten")),
+            Token::Ident("ten"),
             Token::Assign,
             Token::Int(10),
             Token::Semicolon,
             Token::Reaction,
-            Token::Ident(String::from("add")),
+            Token::Ident("add"),
             Token::LParen,
-            Token::Ident(String::from("x")),
+            Token::Ident("x"),
             Token::Comma,
-            Token::Ident(String::from("y")),
+            Token::Ident("y"),
             Token::RParen,
             Token::LBrace,
-            Token::Ident(String::from("x")),
+            Token::Ident("x"),
             Token::Plus,
-            Token::Ident(String::from("y")),
+            Token::Ident("y"),
             Token::Semicolon,
             Token::RBrace,
             Token::Semicolon,
             Token::Molecule,
-            Token::Ident(String::from("result")),
+            Token::Ident("result"),
             Token::Assign,
-            Token::Ident(String::from("add")),
+            Token::Ident("add"),
             Token::LParen,
-            Token::Ident(String::from("five")),
+            Token::Ident("five"),
             Token::Comma,
-            Token::Ident(String::from("ten")),
+            Token::Ident("ten"),
             Token::RParen,
             Token::Semicolon,
             Token::Bang,
@@ -341,8 +845,8 @@ mod tests {
             Token::NotEq,
             Token::Int(9),
             Token::Semicolon,
-            Token::String(String::from("foobar")),
-            Token::String(String::from("foo bar")),
+            Token::String("foobar".into()),
+            Token::String("foo bar".into()),
             Token::LBracket,
             Token::Int(1),
             Token::Comma,
@@ -350,9 +854,9 @@ mod tests {
             Token::RBracket,
             Token::Semicolon,
             Token::LBrace,
-            Token::String(String::from("foo")),
+            Token::String("foo".into()),
             Token::Colon,
-            Token::String(String::from("bar")),
+            Token::String("bar".into()),
             Token::RBrace,
             Token::Eof,
         ];
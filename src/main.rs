@@ -1,4 +1,5 @@
 pub mod lexer;
+pub mod parser;
 
 fn main() {
     println!("Welcome to AtomScript!");
@@ -7,13 +8,12 @@ fn main() {
     let mut input = String::new();
     loop {
         std::io::stdin().read_line(&mut input).unwrap();
-        let mut lexer = lexer::Lexer::new(input.clone());
-        loop {
-            let token = lexer.next_token();
+        let mut lexer = lexer::Lexer::new(&input);
+        for token in lexer.by_ref() {
             println!("{:?}", token);
-            if token == lexer::Token::Eof {
-                break;
-            }
+        }
+        for error in &lexer.errors {
+            eprintln!("{}", error.render(&input));
         }
         input.clear();
     }
@@ -0,0 +1,529 @@
+pub mod ast;
+
+use crate::lexer::{Lexer, Span, Token};
+use ast::{
+    AtomStatement, BlockStatement, Expression, ExpressionStatement, MoleculeStatement, Program,
+    ProduceStatement, ReactionLiteral, Statement,
+};
+
+/// Operator binding power, ordered from loosest (`Lowest`) to tightest
+/// (`Index`). The Pratt loop keeps folding infix operators while the peek
+/// token binds tighter than the current expression's precedence.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Precedence {
+    Lowest,
+    Equals,
+    LessGreater,
+    Sum,
+    Product,
+    Prefix,
+    Call,
+    Index,
+}
+
+/// The binding power a token has as an infix operator, or `Lowest` for tokens
+/// that do not start an infix expression.
+fn precedence_of(token: &Token) -> Precedence {
+    match token {
+        Token::Eq | Token::NotEq => Precedence::Equals,
+        Token::Lt | Token::Gt => Precedence::LessGreater,
+        Token::Plus | Token::Minus => Precedence::Sum,
+        Token::Slash | Token::Asterisk => Precedence::Product,
+        Token::LParen => Precedence::Call,
+        Token::LBracket => Precedence::Index,
+        _ => Precedence::Lowest,
+    }
+}
+
+/// A syntax error collected during parsing, paired with the span it occurred
+/// at so diagnostics can point back into the source.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParseError {
+    pub message: String,
+    pub span: Span,
+}
+
+pub struct Parser<'src> {
+    lexer: Lexer<'src>,
+    current: (Token<'src>, Span),
+    peek: (Token<'src>, Span),
+    pub errors: Vec<ParseError>,
+}
+
+impl<'src> Parser<'src> {
+    pub fn new(mut lexer: Lexer<'src>) -> Self {
+        let current = lexer.next_spanned();
+        let peek = lexer.next_spanned();
+        Self {
+            lexer,
+            current,
+            peek,
+            errors: Vec::new(),
+        }
+    }
+
+    fn next_token(&mut self) {
+        std::mem::swap(&mut self.current, &mut self.peek);
+        self.peek = self.lexer.next_spanned();
+    }
+
+    fn current_is(&self, token: &Token) -> bool {
+        std::mem::discriminant(&self.current.0) == std::mem::discriminant(token)
+    }
+
+    fn peek_is(&self, token: &Token) -> bool {
+        std::mem::discriminant(&self.peek.0) == std::mem::discriminant(token)
+    }
+
+    /// Advance if the peek token matches `token`, otherwise record an error and
+    /// leave the cursor where it is.
+    fn expect_peek(&mut self, token: &Token) -> bool {
+        if self.peek_is(token) {
+            self.next_token();
+            true
+        } else {
+            self.peek_error(token);
+            false
+        }
+    }
+
+    fn peek_error(&mut self, token: &Token) {
+        self.errors.push(ParseError {
+            message: format!("expected next token to be {:?}, got {:?}", token, self.peek.0),
+            span: self.peek.1,
+        });
+    }
+
+    fn peek_precedence(&self) -> Precedence {
+        precedence_of(&self.peek.0)
+    }
+
+    /// Drive the lexer to `Eof`, collecting statements and accumulating errors
+    /// rather than panicking on the first malformed one.
+    pub fn parse_program(&mut self) -> Program<'src> {
+        let mut statements = Vec::new();
+
+        while self.current.0 != Token::Eof {
+            if let Some(statement) = self.parse_statement() {
+                statements.push(statement);
+            }
+            self.next_token();
+        }
+
+        Program { statements }
+    }
+
+    fn parse_statement(&mut self) -> Option<Statement<'src>> {
+        match self.current.0 {
+            Token::Atom => self.parse_atom_statement().map(Statement::Atom),
+            Token::Molecule => self.parse_molecule_statement().map(Statement::Molecule),
+            Token::Produce => self.parse_produce_statement().map(Statement::Produce),
+            _ => self
+                .parse_expression_statement()
+                .map(Statement::Expression),
+        }
+    }
+
+    fn parse_binding_name(&mut self) -> Option<&'src str> {
+        let name = match &self.peek.0 {
+            Token::Ident(name) => *name,
+            _ => {
+                self.peek_error(&Token::Ident(""));
+                return None;
+            }
+        };
+        self.next_token();
+        Some(name)
+    }
+
+    fn parse_atom_statement(&mut self) -> Option<AtomStatement<'src>> {
+        let name = self.parse_binding_name()?;
+        if !self.expect_peek(&Token::Assign) {
+            return None;
+        }
+        self.next_token();
+        let value = self.parse_expression(Precedence::Lowest)?;
+        if self.peek_is(&Token::Semicolon) {
+            self.next_token();
+        }
+        Some(AtomStatement { name, value })
+    }
+
+    fn parse_molecule_statement(&mut self) -> Option<MoleculeStatement<'src>> {
+        let name = self.parse_binding_name()?;
+        if !self.expect_peek(&Token::Assign) {
+            return None;
+        }
+        self.next_token();
+        let value = self.parse_expression(Precedence::Lowest)?;
+        if self.peek_is(&Token::Semicolon) {
+            self.next_token();
+        }
+        Some(MoleculeStatement { name, value })
+    }
+
+    fn parse_produce_statement(&mut self) -> Option<ProduceStatement<'src>> {
+        self.next_token();
+        let value = self.parse_expression(Precedence::Lowest)?;
+        if self.peek_is(&Token::Semicolon) {
+            self.next_token();
+        }
+        Some(ProduceStatement { value })
+    }
+
+    fn parse_expression_statement(&mut self) -> Option<ExpressionStatement<'src>> {
+        let expression = self.parse_expression(Precedence::Lowest)?;
+        if self.peek_is(&Token::Semicolon) {
+            self.next_token();
+        }
+        Some(ExpressionStatement { expression })
+    }
+
+    fn parse_expression(&mut self, precedence: Precedence) -> Option<Expression<'src>> {
+        let mut left = self.parse_prefix()?;
+
+        while !self.peek_is(&Token::Semicolon) && precedence < self.peek_precedence() {
+            self.next_token();
+            left = self.parse_infix(left)?;
+        }
+
+        Some(left)
+    }
+
+    fn parse_prefix(&mut self) -> Option<Expression<'src>> {
+        match self.current.0 {
+            Token::Ident(name) => Some(Expression::Identifier(name)),
+            Token::Int(value) => Some(Expression::Integer(value)),
+            Token::Float(value) => Some(Expression::Float(value)),
+            Token::Char(value) => Some(Expression::CharLiteral(value)),
+            Token::String(ref value) => Some(Expression::StringLiteral(value.clone())),
+            Token::True => Some(Expression::Boolean(true)),
+            Token::False => Some(Expression::Boolean(false)),
+            Token::Bang | Token::Minus => self.parse_prefix_expression(),
+            Token::LParen => self.parse_grouped_expression(),
+            Token::If => self.parse_if_expression(),
+            Token::Reaction => self.parse_reaction_literal().map(Expression::Reaction),
+            Token::LBracket => self
+                .parse_expression_list(&Token::RBracket)
+                .map(Expression::Array),
+            Token::LBrace => self.parse_hash_literal(),
+            _ => {
+                self.errors.push(ParseError {
+                    message: format!("no prefix parse rule for {:?}", self.current.0),
+                    span: self.current.1,
+                });
+                None
+            }
+        }
+    }
+
+    fn parse_infix(&mut self, left: Expression<'src>) -> Option<Expression<'src>> {
+        match self.current.0 {
+            Token::Plus
+            | Token::Minus
+            | Token::Slash
+            | Token::Asterisk
+            | Token::Eq
+            | Token::NotEq
+            | Token::Lt
+            | Token::Gt => self.parse_infix_expression(left),
+            Token::LParen => self.parse_call_expression(left),
+            Token::LBracket => self.parse_index_expression(left),
+            _ => Some(left),
+        }
+    }
+
+    fn parse_prefix_expression(&mut self) -> Option<Expression<'src>> {
+        let operator = self.current.0.clone();
+        self.next_token();
+        let right = self.parse_expression(Precedence::Prefix)?;
+        Some(Expression::Prefix {
+            operator,
+            right: Box::new(right),
+        })
+    }
+
+    fn parse_infix_expression(&mut self, left: Expression<'src>) -> Option<Expression<'src>> {
+        let operator = self.current.0.clone();
+        let precedence = precedence_of(&operator);
+        self.next_token();
+        let right = self.parse_expression(precedence)?;
+        Some(Expression::Infix {
+            left: Box::new(left),
+            operator,
+            right: Box::new(right),
+        })
+    }
+
+    fn parse_grouped_expression(&mut self) -> Option<Expression<'src>> {
+        self.next_token();
+        let expression = self.parse_expression(Precedence::Lowest)?;
+        if !self.expect_peek(&Token::RParen) {
+            return None;
+        }
+        Some(expression)
+    }
+
+    fn parse_if_expression(&mut self) -> Option<Expression<'src>> {
+        if !self.expect_peek(&Token::LParen) {
+            return None;
+        }
+        self.next_token();
+        let condition = self.parse_expression(Precedence::Lowest)?;
+        if !self.expect_peek(&Token::RParen) {
+            return None;
+        }
+        if !self.expect_peek(&Token::LBrace) {
+            return None;
+        }
+        let consequence = self.parse_block_statement();
+
+        let alternative = if self.peek_is(&Token::Else) {
+            self.next_token();
+            if !self.expect_peek(&Token::LBrace) {
+                return None;
+            }
+            Some(self.parse_block_statement())
+        } else {
+            None
+        };
+
+        Some(Expression::If {
+            condition: Box::new(condition),
+            consequence,
+            alternative,
+        })
+    }
+
+    fn parse_block_statement(&mut self) -> BlockStatement<'src> {
+        let mut statements = Vec::new();
+        self.next_token();
+
+        while !self.current_is(&Token::RBrace) && self.current.0 != Token::Eof {
+            if let Some(statement) = self.parse_statement() {
+                statements.push(statement);
+            }
+            self.next_token();
+        }
+
+        BlockStatement { statements }
+    }
+
+    fn parse_reaction_literal(&mut self) -> Option<ReactionLiteral<'src>> {
+        let name = if let Token::Ident(name) = &self.peek.0 {
+            let name = *name;
+            self.next_token();
+            Some(name)
+        } else {
+            None
+        };
+
+        if !self.expect_peek(&Token::LParen) {
+            return None;
+        }
+        let parameters = self.parse_reaction_parameters()?;
+        if !self.expect_peek(&Token::LBrace) {
+            return None;
+        }
+        let body = self.parse_block_statement();
+
+        Some(ReactionLiteral {
+            name,
+            parameters,
+            body,
+        })
+    }
+
+    fn parse_reaction_parameters(&mut self) -> Option<Vec<&'src str>> {
+        let mut parameters = Vec::new();
+
+        if self.peek_is(&Token::RParen) {
+            self.next_token();
+            return Some(parameters);
+        }
+
+        self.next_token();
+        match &self.current.0 {
+            Token::Ident(name) => parameters.push(*name),
+            _ => {
+                self.parameter_error();
+                return None;
+            }
+        }
+
+        while self.peek_is(&Token::Comma) {
+            self.next_token();
+            self.next_token();
+            match &self.current.0 {
+                Token::Ident(name) => parameters.push(*name),
+                _ => {
+                    self.parameter_error();
+                    return None;
+                }
+            }
+        }
+
+        if !self.expect_peek(&Token::RParen) {
+            return None;
+        }
+
+        Some(parameters)
+    }
+
+    fn parameter_error(&mut self) {
+        self.errors.push(ParseError {
+            message: format!("expected parameter name, got {:?}", self.current.0),
+            span: self.current.1,
+        });
+    }
+
+    fn parse_call_expression(&mut self, function: Expression<'src>) -> Option<Expression<'src>> {
+        let arguments = self.parse_expression_list(&Token::RParen)?;
+        Some(Expression::Call {
+            function: Box::new(function),
+            arguments,
+        })
+    }
+
+    fn parse_index_expression(&mut self, left: Expression<'src>) -> Option<Expression<'src>> {
+        self.next_token();
+        let index = self.parse_expression(Precedence::Lowest)?;
+        if !self.expect_peek(&Token::RBracket) {
+            return None;
+        }
+        Some(Expression::Index {
+            left: Box::new(left),
+            index: Box::new(index),
+        })
+    }
+
+    fn parse_expression_list(&mut self, end: &Token) -> Option<Vec<Expression<'src>>> {
+        let mut list = Vec::new();
+
+        if self.peek_is(end) {
+            self.next_token();
+            return Some(list);
+        }
+
+        self.next_token();
+        list.push(self.parse_expression(Precedence::Lowest)?);
+
+        while self.peek_is(&Token::Comma) {
+            self.next_token();
+            self.next_token();
+            list.push(self.parse_expression(Precedence::Lowest)?);
+        }
+
+        if !self.expect_peek(end) {
+            return None;
+        }
+
+        Some(list)
+    }
+
+    fn parse_hash_literal(&mut self) -> Option<Expression<'src>> {
+        let mut pairs = Vec::new();
+
+        while !self.peek_is(&Token::RBrace) {
+            self.next_token();
+            let key = self.parse_expression(Precedence::Lowest)?;
+            if !self.expect_peek(&Token::Colon) {
+                return None;
+            }
+            self.next_token();
+            let value = self.parse_expression(Precedence::Lowest)?;
+            pairs.push((key, value));
+
+            if !self.peek_is(&Token::RBrace) && !self.expect_peek(&Token::Comma) {
+                return None;
+            }
+        }
+
+        if !self.expect_peek(&Token::RBrace) {
+            return None;
+        }
+
+        Some(Expression::Hash(pairs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Program<'_> {
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program();
+        assert!(
+            parser.errors.is_empty(),
+            "parser had errors: {:?}",
+            parser.errors
+        );
+        program
+    }
+
+    #[test]
+    fn test_atom_and_molecule_statements() {
+        let program = parse("atom x = 5; molecule y = 10;");
+
+        assert_eq!(
+            program.statements,
+            vec![
+                Statement::Atom(AtomStatement {
+                    name: "x",
+                    value: Expression::Integer(5),
+                }),
+                Statement::Molecule(MoleculeStatement {
+                    name: "y",
+                    value: Expression::Integer(10),
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_operator_precedence() {
+        let program = parse("produce 1 + 2 * 3;");
+
+        let expected = Expression::Infix {
+            left: Box::new(Expression::Integer(1)),
+            operator: Token::Plus,
+            right: Box::new(Expression::Infix {
+                left: Box::new(Expression::Integer(2)),
+                operator: Token::Asterisk,
+                right: Box::new(Expression::Integer(3)),
+            }),
+        };
+
+        assert_eq!(
+            program.statements,
+            vec![Statement::Produce(ProduceStatement { value: expected })]
+        );
+    }
+
+    #[test]
+    fn test_reaction_and_call() {
+        let program = parse("reaction add(x, y) { produce x + y; } add(1, 2);");
+
+        assert_eq!(program.statements.len(), 2);
+        match &program.statements[0] {
+            Statement::Expression(ExpressionStatement {
+                expression: Expression::Reaction(reaction),
+            }) => {
+                assert_eq!(reaction.name, Some("add"));
+                assert_eq!(reaction.parameters, vec!["x", "y"]);
+                assert_eq!(reaction.body.statements.len(), 1);
+            }
+            other => panic!("expected reaction literal, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_collects_errors_without_panicking() {
+        let lexer = Lexer::new("atom = 5;");
+        let mut parser = Parser::new(lexer);
+        parser.parse_program();
+        assert!(!parser.errors.is_empty());
+    }
+}
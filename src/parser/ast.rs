@@ -0,0 +1,93 @@
+use std::borrow::Cow;
+
+use crate::lexer::Token;
+
+/// A parsed AtomScript program: the sequence of top-level statements produced
+/// by [`Parser::parse_program`](crate::parser::Parser::parse_program).
+#[derive(Debug, PartialEq, Clone)]
+pub struct Program<'src> {
+    pub statements: Vec<Statement<'src>>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Statement<'src> {
+    Atom(AtomStatement<'src>),
+    Molecule(MoleculeStatement<'src>),
+    Produce(ProduceStatement<'src>),
+    Expression(ExpressionStatement<'src>),
+}
+
+/// `atom <name> = <value>;`
+#[derive(Debug, PartialEq, Clone)]
+pub struct AtomStatement<'src> {
+    pub name: &'src str,
+    pub value: Expression<'src>,
+}
+
+/// `molecule <name> = <value>;`
+#[derive(Debug, PartialEq, Clone)]
+pub struct MoleculeStatement<'src> {
+    pub name: &'src str,
+    pub value: Expression<'src>,
+}
+
+/// `produce <value>;`
+#[derive(Debug, PartialEq, Clone)]
+pub struct ProduceStatement<'src> {
+    pub value: Expression<'src>,
+}
+
+/// A bare expression used in statement position.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ExpressionStatement<'src> {
+    pub expression: Expression<'src>,
+}
+
+/// A `{ ... }` block of statements, e.g. a reaction body or an `if` branch.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BlockStatement<'src> {
+    pub statements: Vec<Statement<'src>>,
+}
+
+/// `reaction [name](<params>) { <body> }`
+#[derive(Debug, PartialEq, Clone)]
+pub struct ReactionLiteral<'src> {
+    pub name: Option<&'src str>,
+    pub parameters: Vec<&'src str>,
+    pub body: BlockStatement<'src>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Expression<'src> {
+    Integer(isize),
+    Float(f64),
+    CharLiteral(char),
+    StringLiteral(Cow<'src, str>),
+    Boolean(bool),
+    Identifier(&'src str),
+    Prefix {
+        operator: Token<'src>,
+        right: Box<Expression<'src>>,
+    },
+    Infix {
+        left: Box<Expression<'src>>,
+        operator: Token<'src>,
+        right: Box<Expression<'src>>,
+    },
+    If {
+        condition: Box<Expression<'src>>,
+        consequence: BlockStatement<'src>,
+        alternative: Option<BlockStatement<'src>>,
+    },
+    Reaction(ReactionLiteral<'src>),
+    Call {
+        function: Box<Expression<'src>>,
+        arguments: Vec<Expression<'src>>,
+    },
+    Index {
+        left: Box<Expression<'src>>,
+        index: Box<Expression<'src>>,
+    },
+    Array(Vec<Expression<'src>>),
+    Hash(Vec<(Expression<'src>, Expression<'src>)>),
+}